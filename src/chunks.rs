@@ -0,0 +1,105 @@
+//! Iterators over non-overlapping [`UnsafeCellSlice`] chunks, returned by
+//! [`UnsafeCellSlice::chunks_mut`] and [`UnsafeCellSlice::chunks_exact_mut`].
+
+use crate::UnsafeCellSlice;
+use std::cell::UnsafeCell;
+
+/// An iterator over non-overlapping [`UnsafeCellSlice`] chunks, each of `chunk_size` elements
+/// except possibly the last, which may be shorter.
+///
+/// Returned by [`UnsafeCellSlice::chunks_mut`].
+pub struct ChunksMut<'a, T> {
+    inner: std::slice::Chunks<'a, UnsafeCell<T>>,
+    #[cfg(debug_assertions)]
+    tracker: crate::overlap::BorrowTracker,
+}
+
+impl<'a, T> ChunksMut<'a, T> {
+    #[cfg(debug_assertions)]
+    pub(crate) fn new(
+        inner: std::slice::Chunks<'a, UnsafeCell<T>>,
+        tracker: crate::overlap::BorrowTracker,
+    ) -> Self {
+        Self { inner, tracker }
+    }
+
+    #[cfg(not(debug_assertions))]
+    pub(crate) fn new(inner: std::slice::Chunks<'a, UnsafeCell<T>>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a, T> Iterator for ChunksMut<'a, T> {
+    type Item = UnsafeCellSlice<'a, T>;
+
+    #[cfg(debug_assertions)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|chunk| UnsafeCellSlice::from_cells_with_tracker(chunk, self.tracker.clone()))
+    }
+
+    #[cfg(not(debug_assertions))]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|chunk| UnsafeCellSlice::from_cells_with_tracker(chunk))
+    }
+}
+
+/// An iterator over non-overlapping [`UnsafeCellSlice`] chunks of exactly `chunk_size` elements.
+///
+/// Returned by [`UnsafeCellSlice::chunks_exact_mut`]; any remainder is accessible via
+/// [`ChunksExactMut::remainder`].
+pub struct ChunksExactMut<'a, T> {
+    inner: std::slice::ChunksExact<'a, UnsafeCell<T>>,
+    #[cfg(debug_assertions)]
+    tracker: crate::overlap::BorrowTracker,
+}
+
+impl<'a, T> ChunksExactMut<'a, T> {
+    #[cfg(debug_assertions)]
+    pub(crate) fn new(
+        inner: std::slice::ChunksExact<'a, UnsafeCell<T>>,
+        tracker: crate::overlap::BorrowTracker,
+    ) -> Self {
+        Self { inner, tracker }
+    }
+
+    #[cfg(not(debug_assertions))]
+    pub(crate) fn new(inner: std::slice::ChunksExact<'a, UnsafeCell<T>>) -> Self {
+        Self { inner }
+    }
+
+    /// Return the remainder of the underlying slice that is not covered by any chunk.
+    #[must_use]
+    #[cfg(debug_assertions)]
+    pub fn remainder(&self) -> UnsafeCellSlice<'a, T> {
+        UnsafeCellSlice::from_cells_with_tracker(self.inner.remainder(), self.tracker.clone())
+    }
+
+    /// Return the remainder of the underlying slice that is not covered by any chunk.
+    #[must_use]
+    #[cfg(not(debug_assertions))]
+    pub fn remainder(&self) -> UnsafeCellSlice<'a, T> {
+        UnsafeCellSlice::from_cells_with_tracker(self.inner.remainder())
+    }
+}
+
+impl<'a, T> Iterator for ChunksExactMut<'a, T> {
+    type Item = UnsafeCellSlice<'a, T>;
+
+    #[cfg(debug_assertions)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|chunk| UnsafeCellSlice::from_cells_with_tracker(chunk, self.tracker.clone()))
+    }
+
+    #[cfg(not(debug_assertions))]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|chunk| UnsafeCellSlice::from_cells_with_tracker(chunk))
+    }
+}
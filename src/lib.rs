@@ -26,10 +26,10 @@
 //! let mut data = vec![0u8; 2];
 //! {
 //!     let data = UnsafeCellSlice::new(&mut data);
-//!     let data_a: &mut u8 = unsafe { data.index_mut(0) };
-//!     let data_b: &mut u8 = unsafe { data.index_mut(1) };
-//!     *data_a = 0;
-//!     *data_b = 1;
+//!     unsafe {
+//!         *data.index_mut(0) = 0;
+//!         *data.index_mut(1) = 1;
+//!     }
 //! }
 //! assert_eq!(data[0], 0);
 //! assert_eq!(data[1], 1);
@@ -47,27 +47,98 @@
 //!
 //! Unless you explicitly state otherwise, any contribution intentionally submitted for inclusion in the work by you, as defined in the Apache-2.0 license, shall be dual licensed as above, without any additional terms or conditions.
 
+mod chunks;
 mod slice_index;
 
+#[cfg(debug_assertions)]
+mod overlap;
+
+pub use chunks::{ChunksExactMut, ChunksMut};
 pub use slice_index::SliceIndex;
 
+#[cfg(debug_assertions)]
+pub use overlap::BorrowGuard;
+
+/// The type returned by [`UnsafeCellSlice`]'s mutable accessors.
+///
+/// With `debug_assertions` enabled, this is a [`BorrowGuard`] that records the borrowed range and
+/// panics on overlap with another live borrow; in release builds it is a plain reference and the
+/// bookkeeping compiles away to nothing.
+#[cfg(debug_assertions)]
+type MutRef<'a, U> = BorrowGuard<'a, U>;
+#[cfg(not(debug_assertions))]
+type MutRef<'a, U> = &'a mut U;
+
 /// An unsafe cell slice. Permits acquisition of multiple mutable references of a slice.
 ///
 /// This is inherently unsafe.
 /// It is the responsibility of the caller to only access non-overlapping subslices/elements to avoid data races and undefined behavior.
-#[derive(Copy, Clone)]
-pub struct UnsafeCellSlice<'a, T>(&'a [std::cell::UnsafeCell<T>]);
+///
+/// With `debug_assertions` enabled, overlapping mutable borrows handed out through the same
+/// [`UnsafeCellSlice`] (and its clones) are detected and turned into a panic instead of silent
+/// undefined behavior; see [`get_mut`](Self::get_mut).
+///
+/// Note this is no longer [`Copy`]: the debug-mode borrow tracker it now carries isn't, and a
+/// type that is `Copy` in release builds but not in debug builds would make code compile or not
+/// depending on the consuming crate's build profile. Use [`Clone`] (a cheap pointer/Arc clone)
+/// where an implicit copy was previously relied upon.
+#[derive(Clone)]
+pub struct UnsafeCellSlice<'a, T> {
+    slice: &'a [std::cell::UnsafeCell<T>],
+    #[cfg(debug_assertions)]
+    borrows: overlap::BorrowTracker,
+}
 
 unsafe impl<T: Send + Sync> Send for UnsafeCellSlice<'_, T> {}
 unsafe impl<T: Send + Sync> Sync for UnsafeCellSlice<'_, T> {}
 
 impl<'a, T> UnsafeCellSlice<'a, T> {
+    /// Wrap an already-constructed slice of cells, starting with no borrows recorded.
+    ///
+    /// Used by the top-level constructors, each of which wraps a fresh, independent buffer.
+    pub(crate) fn from_cells(slice: &'a [std::cell::UnsafeCell<T>]) -> Self {
+        Self {
+            slice,
+            #[cfg(debug_assertions)]
+            borrows: overlap::BorrowTracker::default(),
+        }
+    }
+
+    /// Wrap a sub-slice of cells carved out of `self`, sharing `self`'s borrow tracker so that
+    /// overlap detection still catches a borrow taken through the sub-slice aliasing a borrow
+    /// taken through `self` (or another sub-slice derived from it).
+    ///
+    /// Used by [`split_at`](Self::split_at), [`chunks_mut`](Self::chunks_mut) and
+    /// [`chunks_exact_mut`](Self::chunks_exact_mut).
+    pub(crate) fn from_cells_with_tracker(
+        slice: &'a [std::cell::UnsafeCell<T>],
+        #[cfg(debug_assertions)] tracker: overlap::BorrowTracker,
+    ) -> Self {
+        Self {
+            slice,
+            #[cfg(debug_assertions)]
+            borrows: tracker,
+        }
+    }
+
+    /// Wrap a sub-slice of cells carved out of `self`, sharing `self`'s borrow tracker.
+    #[cfg(debug_assertions)]
+    fn child(&self, slice: &'a [std::cell::UnsafeCell<T>]) -> Self {
+        Self::from_cells_with_tracker(slice, self.borrows.clone())
+    }
+
+    /// Wrap a sub-slice of cells carved out of `self`, sharing `self`'s borrow tracker.
+    #[cfg(not(debug_assertions))]
+    fn child(&self, slice: &'a [std::cell::UnsafeCell<T>]) -> Self {
+        Self::from_cells_with_tracker(slice)
+    }
+
     /// Create a new [`UnsafeCellSlice`] from a mutable slice.
     #[must_use]
     pub fn new(slice: &'a mut [T]) -> Self {
         // Rust 1.76: std::ptr::from_mut::<[T]>(slice)
         let ptr = slice as *mut [T] as *const [std::cell::UnsafeCell<T>];
-        Self(unsafe { &*ptr })
+        Self::from_cells(unsafe { &*ptr })
     }
 
     /// Create a new [`UnsafeCellSlice`] from the spare capacity in a [`Vec`].
@@ -76,10 +147,32 @@ impl<'a, T> UnsafeCellSlice<'a, T> {
         Self::new(unsafe { vec_spare_capacity_to_mut_slice(vec) })
     }
 
+    /// Create a new [`UnsafeCellSlice`] from a raw pointer and length, for wrapping
+    /// externally-owned memory (e.g. FFI buffers, mmap regions) that was never a Rust slice to
+    /// begin with.
+    ///
+    /// # Safety
+    /// Same preconditions as [`std::slice::from_raw_parts_mut`]: `ptr` must be non-null, valid for
+    /// reads and writes for `len * size_of::<T>()` bytes for the lifetime `'a`, and properly
+    /// aligned. With `debug_assertions` enabled, non-nullness and alignment are checked eagerly.
+    #[must_use]
+    pub unsafe fn from_raw_parts(ptr: *mut T, len: usize) -> Self {
+        debug_assert!(!ptr.is_null(), "UnsafeCellSlice::from_raw_parts: ptr is null");
+        debug_assert_eq!(
+            ptr as usize % std::mem::align_of::<T>(),
+            0,
+            "UnsafeCellSlice::from_raw_parts: ptr is not aligned for T"
+        );
+        Self::from_cells(std::slice::from_raw_parts(
+            ptr.cast::<std::cell::UnsafeCell<T>>(),
+            len,
+        ))
+    }
+
     /// Return the length of the underlying slice.
     #[must_use]
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.slice.len()
     }
 
     /// Reutrn whether the underlying slice is empty.
@@ -88,20 +181,36 @@ impl<'a, T> UnsafeCellSlice<'a, T> {
         self.len() == 0
     }
 
+    /// Wrap `value`, a reference into the underlying slice, as the return value of a mutable
+    /// accessor, recording its borrowed range when `debug_assertions` are enabled.
+    #[cfg(debug_assertions)]
+    fn track<'s, U: ?Sized>(&'s self, value: &'s mut U) -> MutRef<'s, U> {
+        let range = overlap::range_of(value);
+        BorrowGuard::new(value, range, self.borrows.clone())
+    }
+
+    #[cfg(not(debug_assertions))]
+    fn track<'s, U: ?Sized>(&'s self, value: &'s mut U) -> MutRef<'s, U> {
+        value
+    }
+
     /// Get a mutable reference to a subslice or element of the underlying slice.
     ///
     /// Returns `None` if the index is out of bounds.
     ///
+    /// With `debug_assertions` enabled, this panics if the resolved range overlaps a borrow that
+    /// is still live (i.e. whose [`BorrowGuard`] has not been dropped yet).
+    ///
     /// # Safety
     /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
     /// It is the responsibility of the caller to only access non-overlapping subslices to avoid data races and undefined behavior.
     #[must_use]
     #[allow(clippy::mut_from_ref)]
-    pub unsafe fn get_mut<I>(&self, index: I) -> Option<&mut I::Output>
+    pub unsafe fn get_mut<I>(&self, index: I) -> Option<MutRef<'_, I::Output>>
     where
         I: SliceIndex<T>,
     {
-        index.get_mut(self)
+        Some(self.track(index.get_mut(self)?))
     }
 
     /// Get a mutable reference to a subslice or element of the underlying slice.
@@ -115,13 +224,159 @@ impl<'a, T> UnsafeCellSlice<'a, T> {
     ///
     /// # Panics
     /// May panic if the index is out of bounds.
+    /// With `debug_assertions` enabled, also panics if the resolved range overlaps a borrow that
+    /// is still live (i.e. whose [`BorrowGuard`] has not been dropped yet).
     #[must_use]
     #[allow(clippy::mut_from_ref)]
-    pub unsafe fn index_mut<I>(&self, index: I) -> &mut I::Output
+    pub unsafe fn index_mut<I>(&self, index: I) -> MutRef<'_, I::Output>
     where
         I: SliceIndex<T>,
     {
-        index.index_mut(self)
+        self.track(index.index_mut(self))
+    }
+
+    /// Get a mutable reference to the entire underlying slice.
+    ///
+    /// # Safety
+    /// This is very unsafe because it is capable of creating multiple mutable references to the same data.
+    /// It is the responsibility of the caller to ensure no other references (mutable or shared) to the underlying slice are live at the same time.
+    #[must_use]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn as_mut_slice(&self) -> MutRef<'_, [T]> {
+        let ptr = self.slice.as_ptr() as *mut T;
+        let slice = std::slice::from_raw_parts_mut(ptr, self.slice.len());
+        self.track(slice)
+    }
+
+    /// Get a shared reference to the entire underlying slice.
+    ///
+    /// # Safety
+    /// It is the responsibility of the caller to ensure no mutable references to the underlying slice are live at the same time.
+    #[must_use]
+    pub unsafe fn as_slice(&self) -> &[T] {
+        let ptr = self.slice.as_ptr() as *const T;
+        std::slice::from_raw_parts(ptr, self.slice.len())
+    }
+
+    /// Get a mutable reference to a subslice or element of the underlying slice.
+    ///
+    /// Because this takes `&mut self`, no other reference derived through *this* handle can be
+    /// live, so unlike [`get_mut`](Self::get_mut) this is safe to call. Note that this says nothing
+    /// about a clone of this [`UnsafeCellSlice`]: nothing stops a clone from concurrently using
+    /// [`get_mut`](Self::get_mut)/[`index_mut`](Self::index_mut)/[`as_mut_slice`](Self::as_mut_slice)
+    /// to alias the same underlying elements; `&mut self` only rules out aliasing through this
+    /// specific handle.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    #[must_use]
+    pub fn get_mut_safe<I>(&mut self, index: I) -> Option<MutRef<'_, I::Output>>
+    where
+        I: SliceIndex<T>,
+    {
+        unsafe { self.get_mut(index) }
+    }
+
+    /// Get a mutable reference to the entire underlying slice.
+    ///
+    /// Because this takes `&mut self`, no other reference derived through *this* handle can be
+    /// live, so unlike [`as_mut_slice`](Self::as_mut_slice) this is safe to call. Note that this
+    /// says nothing about a clone of this [`UnsafeCellSlice`]: nothing stops a clone from
+    /// concurrently using [`get_mut`](Self::get_mut)/[`index_mut`](Self::index_mut)/
+    /// [`as_mut_slice`](Self::as_mut_slice) to alias the same underlying elements; `&mut self` only
+    /// rules out aliasing through this specific handle.
+    #[must_use]
+    pub fn as_mut_slice_safe(&mut self) -> MutRef<'_, [T]> {
+        unsafe { self.as_mut_slice() }
+    }
+
+    /// Get the underlying slice of [`UnsafeCell`](std::cell::UnsafeCell)s.
+    #[must_use]
+    pub fn as_unsafe_cells(&self) -> &[std::cell::UnsafeCell<T>] {
+        self.slice
+    }
+
+    /// Get the underlying slice as a slice of [`Cell`](std::cell::Cell)s.
+    ///
+    /// [`Cell<T>`](std::cell::Cell) has the same memory layout as
+    /// [`UnsafeCell<T>`](std::cell::UnsafeCell), so this cast is sound. [`Cell<T>`](std::cell::Cell)
+    /// being `!Sync` only stops the returned reference itself from crossing a thread boundary; it
+    /// does not stop a second thread from calling `as_cell_slice` on its own clone of this
+    /// [`UnsafeCellSlice`] (which is [`Send`]/[`Sync`]) to obtain an aliasing view of the same
+    /// underlying cells, since `Cell::get`/`Cell::set` are not atomic. So this is just as unsafe as
+    /// [`get_mut`](Self::get_mut)/[`index_mut`](Self::index_mut).
+    ///
+    /// # Safety
+    /// It is the responsibility of the caller to ensure no other mutable or aliasing-unsound access
+    /// (through this [`UnsafeCellSlice`] or a clone of it, on this or another thread) happens while
+    /// the returned slice is used.
+    #[must_use]
+    pub unsafe fn as_cell_slice(&self) -> &[std::cell::Cell<T>] {
+        // SAFETY: `Cell<T>` is a `#[repr(transparent)]` wrapper around `UnsafeCell<T>`, so
+        // `&[UnsafeCell<T>]` and `&[Cell<T>]` are layout-compatible.
+        unsafe {
+            &*(self.slice as *const [std::cell::UnsafeCell<T>] as *const [std::cell::Cell<T>])
+        }
+    }
+
+    /// Split the underlying slice into two at `mid`, returning an [`UnsafeCellSlice`] for each
+    /// half.
+    ///
+    /// Because the two halves index non-overlapping ranges by construction, they can be handed to
+    /// independent threads (e.g. rayon workers) without risking aliasing, unlike manually computed
+    /// indices passed to [`index_mut`](Self::index_mut).
+    ///
+    /// # Panics
+    /// Panics if `mid > self.len()`.
+    #[must_use]
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        let (a, b) = self.slice.split_at(mid);
+        (self.child(a), self.child(b))
+    }
+
+    /// Return an iterator over `chunk_size`-length, non-overlapping [`UnsafeCellSlice`] chunks of
+    /// the underlying slice; the last chunk may be shorter than `chunk_size`.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    #[must_use]
+    #[cfg(debug_assertions)]
+    pub fn chunks_mut(&self, chunk_size: usize) -> ChunksMut<'a, T> {
+        ChunksMut::new(self.slice.chunks(chunk_size), self.borrows.clone())
+    }
+
+    /// Return an iterator over `chunk_size`-length, non-overlapping [`UnsafeCellSlice`] chunks of
+    /// the underlying slice; the last chunk may be shorter than `chunk_size`.
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    #[must_use]
+    #[cfg(not(debug_assertions))]
+    pub fn chunks_mut(&self, chunk_size: usize) -> ChunksMut<'a, T> {
+        ChunksMut::new(self.slice.chunks(chunk_size))
+    }
+
+    /// Return an iterator over `chunk_size`-length, non-overlapping [`UnsafeCellSlice`] chunks of
+    /// the underlying slice; any remainder is accessible via
+    /// [`ChunksExactMut::remainder`].
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    #[must_use]
+    #[cfg(debug_assertions)]
+    pub fn chunks_exact_mut(&self, chunk_size: usize) -> ChunksExactMut<'a, T> {
+        ChunksExactMut::new(self.slice.chunks_exact(chunk_size), self.borrows.clone())
+    }
+
+    /// Return an iterator over `chunk_size`-length, non-overlapping [`UnsafeCellSlice`] chunks of
+    /// the underlying slice; any remainder is accessible via
+    /// [`ChunksExactMut::remainder`].
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is 0.
+    #[must_use]
+    #[cfg(not(debug_assertions))]
+    pub fn chunks_exact_mut(&self, chunk_size: usize) -> ChunksExactMut<'a, T> {
+        ChunksExactMut::new(self.slice.chunks_exact(chunk_size))
     }
 }
 
@@ -155,4 +410,132 @@ mod tests {
             assert!(!data.is_empty());
         }
     }
+
+    #[test]
+    fn slice_views() {
+        let mut data = vec![1u8, 2, 3];
+        let unsafe_cell_slice = super::UnsafeCellSlice::new(&mut data);
+        unsafe {
+            assert_eq!(unsafe_cell_slice.as_slice(), &[1, 2, 3]);
+            unsafe_cell_slice.as_mut_slice()[1] = 5;
+        }
+        assert_eq!(data, vec![1, 5, 3]);
+    }
+
+    #[test]
+    fn non_overlapping_borrows_do_not_panic() {
+        let mut data = vec![0u8; 4];
+        let unsafe_cell_slice = super::UnsafeCellSlice::new(&mut data);
+        unsafe {
+            *unsafe_cell_slice.index_mut(0) = 1;
+            *unsafe_cell_slice.index_mut(1) = 2;
+        }
+        assert_eq!(data, vec![1, 2, 0, 0]);
+    }
+
+    #[test]
+    fn safe_accessors() {
+        let mut data = vec![1u8, 2, 3];
+        let mut unsafe_cell_slice = super::UnsafeCellSlice::new(&mut data);
+        *unsafe_cell_slice.get_mut_safe(0).unwrap() = 9;
+        unsafe_cell_slice.as_mut_slice_safe()[2] = 7;
+        assert_eq!(data, vec![9, 2, 7]);
+    }
+
+    #[test]
+    fn cell_view() {
+        let mut data = vec![1u8, 2, 3];
+        let unsafe_cell_slice = super::UnsafeCellSlice::new(&mut data);
+        assert_eq!(unsafe_cell_slice.as_unsafe_cells().len(), 3);
+
+        let cells = unsafe { unsafe_cell_slice.as_cell_slice() };
+        let previous = cells[0].replace(cells[1].get());
+        assert_eq!(previous, 1);
+        assert_eq!(data, vec![2, 2, 3]);
+    }
+
+    #[test]
+    fn split_and_chunk() {
+        let mut data = vec![0u8, 1, 2, 3, 4];
+        let unsafe_cell_slice = super::UnsafeCellSlice::new(&mut data);
+
+        let (a, b) = unsafe_cell_slice.split_at(2);
+        assert_eq!(a.len(), 2);
+        assert_eq!(b.len(), 3);
+
+        let chunk_lengths: Vec<usize> = unsafe_cell_slice
+            .chunks_mut(2)
+            .map(|chunk| chunk.len())
+            .collect();
+        assert_eq!(chunk_lengths, vec![2, 2, 1]);
+
+        let mut chunks_exact = unsafe_cell_slice.chunks_exact_mut(2);
+        let exact_lengths: Vec<usize> = (&mut chunks_exact).map(|chunk| chunk.len()).collect();
+        assert_eq!(exact_lengths, vec![2, 2]);
+        assert_eq!(chunks_exact.remainder().len(), 1);
+    }
+
+    #[test]
+    fn from_raw_parts() {
+        let mut data = vec![1u8, 2, 3];
+        let unsafe_cell_slice =
+            unsafe { super::UnsafeCellSlice::from_raw_parts(data.as_mut_ptr(), data.len()) };
+        unsafe {
+            *unsafe_cell_slice.index_mut(0) = 9;
+        }
+        assert_eq!(data, vec![9, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "overlapping mutable borrows")]
+    fn overlapping_borrows_panic() {
+        let mut data = vec![0u8; 4];
+        let unsafe_cell_slice = super::UnsafeCellSlice::new(&mut data);
+        unsafe {
+            let _a = unsafe_cell_slice.index_mut(0..3);
+            let _b = unsafe_cell_slice.index_mut(2..4);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "overlapping mutable borrows")]
+    fn overlapping_borrows_across_split_panic() {
+        let mut data = vec![0u8; 4];
+        let unsafe_cell_slice = super::UnsafeCellSlice::new(&mut data);
+        let (first_half, _) = unsafe_cell_slice.split_at(2);
+        unsafe {
+            let _a = unsafe_cell_slice.index_mut(0..4);
+            let _b = first_half.index_mut(0..2);
+        }
+    }
+
+    #[test]
+    fn disjoint_borrows_with_same_local_index_do_not_panic() {
+        // Each half/chunk has its own base pointer, so element 0 of one and element 0 of another
+        // share the same *local* index despite being disjoint in the underlying allocation: the
+        // tracker must compare absolute addresses, not slice-local indices, or this panics.
+        let mut data = vec![0u8; 4];
+        let unsafe_cell_slice = super::UnsafeCellSlice::new(&mut data);
+        let (first_half, second_half) = unsafe_cell_slice.split_at(2);
+        unsafe {
+            // Both borrows are held at once, so the second is checked against the first.
+            #[allow(unused_mut)]
+            let (mut a, mut b) = (first_half.index_mut(0), second_half.index_mut(0));
+            *a = 1;
+            *b = 2;
+        }
+
+        let mut chunks = unsafe_cell_slice.chunks_mut(2);
+        let chunk_a = chunks.next().unwrap();
+        let chunk_b = chunks.next().unwrap();
+        unsafe {
+            #[allow(unused_mut)]
+            let (mut a, mut b) = (chunk_a.index_mut(0), chunk_b.index_mut(0));
+            *a = 3;
+            *b = 4;
+        }
+        assert_eq!(data, vec![3, 0, 4, 0]);
+    }
 }
@@ -0,0 +1,103 @@
+//! Debug-only bookkeeping that catches overlapping mutable borrows handed out through
+//! [`UnsafeCellSlice`](crate::UnsafeCellSlice).
+//!
+//! This module is only compiled in when `debug_assertions` are enabled (see [`crate`]); in
+//! release builds the whole mechanism, including the [`BorrowTracker`] field stored inside
+//! [`UnsafeCellSlice`](crate::UnsafeCellSlice), compiles away entirely.
+
+use std::ops::{Deref, DerefMut, Range};
+use std::sync::{Arc, Mutex};
+
+/// The set of byte address ranges (see [`range_of`]) of an
+/// [`UnsafeCellSlice`](crate::UnsafeCellSlice) that are currently borrowed, shared between all
+/// clones of the same slice and the sub-slices carved out of it.
+#[derive(Default, Clone)]
+pub(crate) struct BorrowTracker(Arc<Mutex<Vec<Range<usize>>>>);
+
+impl BorrowTracker {
+    /// Record `range` as borrowed.
+    ///
+    /// # Panics
+    /// Panics if `range` overlaps a range that is already borrowed.
+    fn acquire(&self, range: Range<usize>) {
+        // Poisoning is expected here: `acquire` itself panics (with the lock held) on overlap,
+        // and a live `BorrowGuard` from before the panic may still call `release` while
+        // unwinding. Treat a poisoned mutex the same as an unpoisoned one rather than
+        // double-panicking, which would abort the process instead of unwinding.
+        let mut borrowed = self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        for other in borrowed.iter() {
+            let non_overlapping = range.end <= other.start || other.end <= range.start;
+            if !non_overlapping {
+                panic!(
+                    "UnsafeCellSlice: overlapping mutable borrows of {range:?} and {other:?}"
+                );
+            }
+        }
+        borrowed.push(range);
+    }
+
+    fn release(&self, range: &Range<usize>) {
+        let mut borrowed = self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(position) = borrowed.iter().position(|borrowed_range| borrowed_range == range)
+        {
+            borrowed.remove(position);
+        }
+    }
+}
+
+/// Compute the byte address range spanned by `value` in the process's address space.
+///
+/// Addresses (rather than indices relative to some slice's own base pointer) are used so that
+/// ranges recorded through different [`UnsafeCellSlice`](crate::UnsafeCellSlice)s that share a
+/// [`BorrowTracker`] (e.g. the two halves of a [`split_at`](crate::UnsafeCellSlice::split_at), or
+/// different [`chunks_mut`](crate::UnsafeCellSlice::chunks_mut) chunks) are directly comparable:
+/// each such view has its own base pointer, so indices relative to it would collide for
+/// legitimately disjoint regions that happen to share the same local offset.
+pub(crate) fn range_of<U: ?Sized>(value: &U) -> Range<usize> {
+    let start = value as *const U as *const u8 as usize;
+    start..start + std::mem::size_of_val(value)
+}
+
+/// A guard tracking a single borrowed range of an [`UnsafeCellSlice`](crate::UnsafeCellSlice).
+///
+/// Returned in place of a plain reference by [`get_mut`](crate::UnsafeCellSlice::get_mut),
+/// [`index_mut`](crate::UnsafeCellSlice::index_mut) and
+/// [`as_mut_slice`](crate::UnsafeCellSlice::as_mut_slice) when `debug_assertions` are enabled.
+/// Dereferences to the borrowed value; dropping the guard releases the tracked range so that a
+/// later, non-overlapping borrow of the same indices is not mistaken for an overlap.
+pub struct BorrowGuard<'a, T: ?Sized> {
+    value: &'a mut T,
+    range: Range<usize>,
+    tracker: BorrowTracker,
+}
+
+impl<'a, T: ?Sized> BorrowGuard<'a, T> {
+    pub(crate) fn new(value: &'a mut T, range: Range<usize>, tracker: BorrowTracker) -> Self {
+        tracker.acquire(range.clone());
+        Self {
+            value,
+            range,
+            tracker,
+        }
+    }
+}
+
+impl<'a, T: ?Sized> Deref for BorrowGuard<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<'a, T: ?Sized> DerefMut for BorrowGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+    }
+}
+
+impl<'a, T: ?Sized> Drop for BorrowGuard<'a, T> {
+    fn drop(&mut self) {
+        self.tracker.release(&self.range);
+    }
+}